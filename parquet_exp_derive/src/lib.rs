@@ -0,0 +1,113 @@
+//! Derive macro for `parquet_exp::record::RecordWriter`.
+//!
+//! `#[derive(ParquetRecord)]` walks a struct's fields in declaration order and generates
+//! both the `schema::types::Type` message and the `Field`-by-field `Row` construction, so
+//! a struct's field order and the Parquet schema's column order can never diverge.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Type};
+
+#[proc_macro_derive(ParquetRecord)]
+pub fn derive_parquet_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let struct_name = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => panic!("ParquetRecord can only be derived for structs with named fields"),
+        },
+        _ => panic!("ParquetRecord can only be derived for structs"),
+    };
+
+    let mut schema_fields = Vec::new();
+    let mut field_exprs = Vec::new();
+
+    for field in fields {
+        let field_ident = field.ident.as_ref().expect("named field");
+        let field_name = field_ident.to_string();
+        let (physical_type, converted_type, field_ctor) = parquet_mapping(&field.ty, field_ident);
+
+        schema_fields.push(quote! {
+            Arc::new(
+                parquet::schema::types::Type::primitive_type_builder(
+                    #field_name,
+                    parquet::basic::Type::#physical_type,
+                )
+                .with_converted_type(parquet::basic::ConvertedType::#converted_type)
+                .with_repetition(parquet::basic::Repetition::REQUIRED)
+                .build()
+                .unwrap(),
+            )
+        });
+
+        field_exprs.push(quote! {
+            (#field_name.to_owned(), #field_ctor)
+        });
+    }
+
+    let expanded = quote! {
+        impl parquet_exp::record::RecordWriter for #struct_name {
+            fn schema() -> std::sync::Arc<parquet::schema::types::Type> {
+                use std::sync::Arc;
+                Arc::new(
+                    parquet::schema::types::Type::group_type_builder("schema")
+                        .with_fields(vec![#(#schema_fields),*])
+                        .build()
+                        .unwrap(),
+                )
+            }
+
+            fn into_row(self) -> parquet::record::Row {
+                parquet_exp::rowwritebuffer::create_row(vec![#(#field_exprs),*])
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Maps a Rust field type to its Parquet physical type, converted type, and the
+/// `parquet::record::Field` constructor expression used to build that column's value.
+fn parquet_mapping(
+    ty: &Type,
+    field_ident: &syn::Ident,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let type_name = quote!(#ty).to_string().replace(' ', "");
+
+    match type_name.as_str() {
+        "i64" => (
+            quote!(INT64),
+            quote!(NONE),
+            quote!(parquet::record::Field::Long(self.#field_ident)),
+        ),
+        "i32" => (
+            quote!(INT32),
+            quote!(NONE),
+            quote!(parquet::record::Field::Int(self.#field_ident)),
+        ),
+        "bool" => (
+            quote!(BOOLEAN),
+            quote!(NONE),
+            quote!(parquet::record::Field::Bool(self.#field_ident)),
+        ),
+        "f64" => (
+            quote!(DOUBLE),
+            quote!(NONE),
+            quote!(parquet::record::Field::Double(self.#field_ident)),
+        ),
+        "String" => (
+            quote!(BYTE_ARRAY),
+            quote!(UTF8),
+            quote!(parquet::record::Field::Str(self.#field_ident)),
+        ),
+        // `type_name` has every space stripped, so `&'static str` arrives as `&'staticstr`.
+        "&str" | "&'staticstr" => (
+            quote!(BYTE_ARRAY),
+            quote!(UTF8),
+            quote!(parquet::record::Field::Str(self.#field_ident.to_owned())),
+        ),
+        other => panic!("ParquetRecord: unsupported field type `{other}`, add a mapping in parquet_exp_derive"),
+    }
+}
@@ -0,0 +1,193 @@
+use std::sync::Arc;
+use parquet::{
+    errors::{ParquetError, Result},
+    record::Row,
+    schema::types::Type
+};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use crate::rowwriter;
+
+/// Async counterpart to `RowWriteBuffer`, for destinations reachable only through
+/// `tokio::io::AsyncWrite` (object stores, network sinks, ...) rather than a plain
+/// `std::io::Write`.
+///
+/// `RowWriteBuffer` gets its concurrency by spawning a dedicated OS thread per file and
+/// pushing `Vec<Row>` over a `sync_channel`; that doesn't fit an async sink, since there
+/// is no thread to block and no `Write` impl to hand it. Instead, `AsyncRowWriteBuffer`
+/// encodes its one row group into a private in-memory parquet buffer (a complete mini
+/// file, footer included) via `rowwriter::RowWriter::from_writer`, then streams that
+/// buffer's bytes out through the `AsyncWrite` sink in chunks bounded by
+/// `max_staging_bytes`.
+///
+/// Each flush produces a standalone parquet file and appends its bytes to the sink
+/// as-is, so a second flush to the same destination would concatenate a second,
+/// independent parquet file onto the first rather than adding a row group to it --
+/// there is no valid way to make that work without a seekable sink to go back and
+/// rewrite the footer. So `group_size` must cover the whole write: construct this with
+/// a `group_size` at least as large as the total row count, and only one flush (whether
+/// triggered by `append_row` filling the buffer or by `close`) ever happens. Any further
+/// flush attempt is rejected with an error instead of silently corrupting the output.
+pub struct AsyncRowWriteBuffer<W: AsyncWrite + Unpin> {
+    schema: Arc<Type>,
+    max_row_group: usize,
+    max_staging_bytes: usize,
+    buffer: Vec<Row>,
+    sink: W,
+    has_flushed: bool,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncRowWriteBuffer<W> {
+
+    /// `max_staging_bytes` caps the size of the encoded row group: `flush` encodes the
+    /// row group into memory in full, and if the result is larger than this it returns
+    /// an error and writes nothing to the sink.
+    pub fn new(sink: W, schema: Arc<Type>, group_size: usize, max_staging_bytes: usize) -> Self {
+        AsyncRowWriteBuffer {
+            schema,
+            max_row_group: group_size,
+            max_staging_bytes,
+            buffer: Vec::with_capacity(group_size),
+            sink,
+            has_flushed: false,
+        }
+    }
+
+    pub fn remaining_space(&self) -> usize {
+        self.max_row_group - self.buffer.len()
+    }
+
+    /// Encodes and streams out the buffered rows as a single row group. Only ever
+    /// valid once per destination -- see the struct doc comment.
+    pub async fn flush(&mut self) -> Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        if self.has_flushed {
+            return Err(ParquetError::General(
+                "AsyncRowWriteBuffer only supports a single row group per destination; \
+                 construct it with group_size >= the total number of rows you intend to write".to_owned()
+            ));
+        }
+
+        let rows = std::mem::take(&mut self.buffer);
+        let row_count = rows.len();
+
+        let mut row_group_writer = rowwriter::RowWriter::from_writer(Vec::new(), self.schema.clone(), row_count, &[])?;
+        for row in rows {
+            row_group_writer.append_row(row);
+        }
+        row_group_writer.flush()?;
+        let encoded = row_group_writer.close()?;
+
+        if encoded.len() > self.max_staging_bytes {
+            return Err(ParquetError::General(format!(
+                "encoded row group ({} bytes) exceeds max_staging_bytes ({})",
+                encoded.len(),
+                self.max_staging_bytes
+            )));
+        }
+
+        for chunk in encoded.chunks(self.max_staging_bytes.max(1)) {
+            self.sink.write_all(chunk).await
+                .map_err(|err| ParquetError::General(format!("async flush failed: {err}")))?;
+        }
+
+        self.has_flushed = true;
+        Ok(())
+    }
+
+    pub async fn append_row(&mut self, row: Row) -> Result<()> {
+        self.buffer.push(row);
+
+        if self.buffer.len() == self.max_row_group {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Flushes any remaining buffered rows and shuts the sink down cleanly.
+    pub async fn close(mut self) -> Result<()> {
+        if !self.buffer.is_empty() {
+            self.flush().await?;
+        }
+        self.sink.shutdown().await
+            .map_err(|err| ParquetError::General(format!("failed to shut down async sink: {err}")))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::{Field, RowAccessor},
+        schema::parser::parse_message_type
+    };
+    use crate::rowwritebuffer::create_row;
+    use super::AsyncRowWriteBuffer;
+
+    const MESSAGE_TYPE: &str = "
+        message schema {
+            REQUIRED INT64 id;
+            REQUIRED BINARY account (UTF8);
+        }
+    ";
+
+    #[tokio::test]
+    async fn writes_a_readable_parquet_file_through_an_async_sink() {
+        let schema = Arc::new(parse_message_type(MESSAGE_TYPE).unwrap());
+        let path = "/tmp/test_async_row_write_buffer.parquet";
+        let rows = [(1_i64, "Hello"), (2, "World"), (3, "!")];
+
+        // group_size covers the whole write, so the one flush fires when the last
+        // append_row fills the buffer and close() only shuts the sink down -- see the
+        // struct doc comment for why a second flush is rejected.
+        let file = tokio::fs::File::create(path).await.unwrap();
+        let mut buffer = AsyncRowWriteBuffer::new(file, schema, rows.len(), 1 << 20);
+        for (id, account) in rows {
+            buffer.append_row(create_row(vec![
+                ("id".to_owned(), Field::Long(id)),
+                ("account".to_owned(), Field::Str(account.to_owned())),
+            ])).await.unwrap();
+        }
+        buffer.close().await.unwrap();
+
+        let reader = SerializedFileReader::new(std::fs::File::open(path).unwrap()).unwrap();
+        let read_back: Vec<(i64, String)> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| {
+                let row = row.unwrap();
+                (row.get_long(0).unwrap(), row.get_string(1).unwrap().to_owned())
+            })
+            .collect();
+
+        assert_eq!(read_back, vec![(1, "Hello".to_owned()), (2, "World".to_owned()), (3, "!".to_owned())]);
+    }
+
+    #[tokio::test]
+    async fn rejects_a_second_row_group_instead_of_corrupting_the_file() {
+        let schema = Arc::new(parse_message_type(MESSAGE_TYPE).unwrap());
+        let path = "/tmp/test_async_row_write_buffer_second_group.parquet";
+
+        // group_size of 1 forces append_row to trigger an automatic flush after the
+        // very first row, so the second row's auto-flush attempt hits the one-row-group
+        // limit.
+        let file = tokio::fs::File::create(path).await.unwrap();
+        let mut buffer = AsyncRowWriteBuffer::new(file, schema, 1, 1 << 20);
+
+        buffer.append_row(create_row(vec![
+            ("id".to_owned(), Field::Long(1)),
+            ("account".to_owned(), Field::Str("Hello".to_owned())),
+        ])).await.unwrap();
+
+        let result = buffer.append_row(create_row(vec![
+            ("id".to_owned(), Field::Long(2)),
+            ("account".to_owned(), Field::Str("World".to_owned())),
+        ])).await;
+
+        assert!(result.is_err());
+    }
+}
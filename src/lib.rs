@@ -0,0 +1,20 @@
+//! Experimental helpers for writing Parquet files from row-oriented Rust data: a
+//! buffered row writer, a `#[derive(ParquetRecord)]` macro, async and S3-backed
+//! sinks, and a streaming k-way merge.
+
+// `#[derive(ParquetRecord)]` expands to `impl parquet_exp::record::RecordWriter for
+// ...`; this alias lets that path resolve when the derive is applied to a struct
+// inside this crate itself (e.g. in our own tests), exactly as it already does for
+// downstream crates that depend on `parquet_exp` normally.
+extern crate self as parquet_exp;
+
+#[path = "writer.rs"]
+pub mod rowwriter;
+pub mod rowwritebuffer;
+pub mod record;
+pub mod async_rowwritebuffer;
+pub mod s3_writer;
+pub mod merge;
+
+pub use parquet_exp_derive::ParquetRecord;
+pub use merge::merge_parquet;
@@ -0,0 +1,245 @@
+use std::fs::File;
+use parquet::{
+    errors::{ParquetError, Result},
+    file::reader::{FileReader, SerializedFileReader},
+    record::Row
+};
+use crate::rowwritebuffer::RowWriteBuffer;
+
+/// Row groups get flushed at this size when streaming the merge output. Arbitrary but
+/// reasonable now that the destination is no longer tied to either input's own group
+/// size.
+const DEFAULT_MERGE_GROUP_SIZE: usize = 8192;
+
+/// A tournament (loser) tree over the current head row of `capacity` input streams,
+/// used to pick the overall-smallest head in O(log capacity) per output row instead of
+/// re-scanning every input.
+///
+/// `tree[0]` always holds the index of the current overall winner. `tree[1..capacity)`
+/// hold, for each internal node, the index of the "loser" of that node's subtree -- the
+/// row that lost its match but may still win further up once its sibling subtree's
+/// winner is consumed. Leaves are addressed virtually at `capacity + leaf_index`, so the
+/// parent of any node `p` (leaf or internal) is simply `p / 2`, with the root at `1`.
+///
+/// A source that has run out of rows is represented by a `None` head, which always
+/// loses any comparison (i.e. compares as +infinity), so it naturally drops out of
+/// contention without special-casing the merge loop.
+///
+/// `sources` owns each input's row iterator, and those iterators borrow their
+/// `SerializedFileReader` for `'a`, so the tree never outlives the readers it reads
+/// from -- `merge_parquet`
+/// keeps those readers alive in its own stack frame for exactly as long as `LoserTree`
+/// needs them, which is all the self-reference-free lifetime threading this problem
+/// actually requires.
+struct LoserTree<'a, F> {
+    sources: Vec<Box<dyn Iterator<Item = Row> + 'a>>,
+    heads: Vec<Option<Row>>,
+    capacity: usize,
+    tree: Vec<usize>,
+    cmp: F
+}
+
+impl<'a, F> LoserTree<'a, F>
+where
+    F: Fn(&Row, &Row) -> bool
+{
+    /// `cmp(a, b)` returns `true` when `a` belongs before `b` in the merged output.
+    fn new(mut sources: Vec<Box<dyn Iterator<Item = Row> + 'a>>, cmp: F) -> Self {
+        let capacity = sources.len().next_power_of_two().max(2);
+
+        let mut heads: Vec<Option<Row>> = sources.iter_mut().map(|source| source.next()).collect();
+        heads.resize_with(capacity, || None);
+
+        let mut tree = LoserTree {
+            sources,
+            heads,
+            capacity,
+            tree: vec![0; capacity],
+            cmp
+        };
+        tree.build();
+        tree
+    }
+
+    /// `true` if leaf `a` wins its match against leaf `b` (i.e. `a` should bubble up).
+    fn wins(&self, a: usize, b: usize) -> bool {
+        match (&self.heads[a], &self.heads[b]) {
+            (None, None) => true,
+            (None, Some(_)) => false,
+            (Some(_), None) => true,
+            (Some(row_a), Some(row_b)) => (self.cmp)(row_a, row_b)
+        }
+    }
+
+    /// Plays every leaf pairwise, bottom-up, to seed `tree` with the initial losers and
+    /// have `tree[0]` name the first overall winner.
+    fn build(&mut self) {
+        let mut winner = vec![0usize; 2 * self.capacity];
+        for leaf in 0..self.capacity {
+            winner[self.capacity + leaf] = leaf;
+        }
+        for node in (1..self.capacity).rev() {
+            let left = winner[2 * node];
+            let right = winner[2 * node + 1];
+            if self.wins(left, right) {
+                winner[node] = left;
+                self.tree[node] = right;
+            } else {
+                winner[node] = right;
+                self.tree[node] = left;
+            }
+        }
+        self.tree[0] = winner[1];
+    }
+
+    /// Re-plays the matches on the path from `leaf` up to the root after `leaf`'s head
+    /// changed, restoring the loser-tree invariant without touching any other leaf.
+    fn replay(&mut self, leaf: usize) {
+        let mut winner = leaf;
+        let mut node = (self.capacity + leaf) / 2;
+        loop {
+            if !self.wins(winner, self.tree[node]) {
+                std::mem::swap(&mut winner, &mut self.tree[node]);
+            }
+            if node == 1 {
+                break;
+            }
+            node /= 2;
+        }
+        self.tree[0] = winner;
+    }
+
+    /// Pops the current overall-smallest row, refills its source, and restores the
+    /// invariant. Returns `None` once every source is exhausted.
+    fn pop(&mut self) -> Option<Row> {
+        let winner = self.tree[0];
+        let row = self.heads[winner].take()?;
+
+        if winner < self.sources.len() {
+            self.heads[winner] = self.sources[winner].next();
+        }
+        self.replay(winner);
+        Some(row)
+    }
+}
+
+/// Streams `paths` into `output` in the order defined by `cmp`, merging any number of
+/// sorted input files through a loser tree (see [`LoserTree`]) so that at most one
+/// buffered row per input is ever held in memory, regardless of input count or size.
+///
+/// `cmp(a, b)` should return `true` when `a` belongs before `b` in the merged output --
+/// the same contract `smaller_test`-style comparators already use for a pairwise merge.
+pub fn merge_parquet<F>(paths: Vec<&str>, output: &str, cmp: F) -> Result<()>
+where
+    F: Fn(&Row, &Row) -> bool
+{
+    if paths.is_empty() {
+        return Err(ParquetError::General("merge_parquet: paths must not be empty".to_owned()));
+    }
+
+    let readers: Vec<SerializedFileReader<File>> = paths
+        .iter()
+        .map(|path| {
+            let file = File::open(path).unwrap_or_else(|err| panic!("Failed to open {path}: {err}"));
+            SerializedFileReader::new(file).unwrap_or_else(|err| panic!("Failed to read {path}: {err}"))
+        })
+        .collect();
+
+    let schema = readers[0].metadata().file_metadata().schema_descr().root_schema_ptr();
+
+    let sources: Vec<Box<dyn Iterator<Item = Row> + '_>> = readers
+        .iter()
+        .map(|reader| {
+            let row_iter = reader.get_row_iter(None).unwrap().map(|row| row.unwrap());
+            Box::new(row_iter) as Box<dyn Iterator<Item = Row> + '_>
+        })
+        .collect();
+
+    let mut tree = LoserTree::new(sources, cmp);
+    let mut out = RowWriteBuffer::new(output, schema, DEFAULT_MERGE_GROUP_SIZE, None, Vec::new())?;
+
+    while let Some(row) = tree.pop() {
+        out.append_row(row);
+    }
+    out.close();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::{Field, RowAccessor},
+        schema::parser::parse_message_type
+    };
+    use crate::rowwriter::RowWriter;
+    use crate::rowwritebuffer::create_row;
+    use super::merge_parquet;
+
+    const MESSAGE_TYPE: &str = "
+        message schema {
+            REQUIRED INT64 id;
+            REQUIRED BINARY account (UTF8);
+        }
+    ";
+
+    fn smaller(row_1: &parquet::record::Row, row_2: &parquet::record::Row) -> bool {
+        row_1.get_long(0).unwrap() <= row_2.get_long(0).unwrap()
+    }
+
+    fn write_source(path: &str, ids: &[i64]) {
+        let schema = std::sync::Arc::new(parse_message_type(MESSAGE_TYPE).unwrap());
+        let mut writer = RowWriter::new(Path::new(path), schema, ids.len() as u64, ids.len().max(1), &[]).unwrap();
+        for &id in ids {
+            writer.append_row(create_row(vec![
+                ("id".to_owned(), Field::Long(id)),
+                ("account".to_owned(), Field::Str(format!("acct-{id}"))),
+            ]));
+        }
+        writer.close().unwrap();
+    }
+
+    fn read_ids(path: &str) -> Vec<i64> {
+        let reader = SerializedFileReader::new(fs::File::open(path).unwrap()).unwrap();
+        reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| row.unwrap().get_long(0).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn merges_more_than_two_sorted_sources_in_order() {
+        // three sources of uneven length, with ties across sources, to exercise both
+        // the +infinity handling of an exhausted leaf and replay() across non-trivial
+        // tree depths (capacity pads 3 sources up to 4 leaves).
+        write_source("/tmp/test_merge_src_a.parquet", &[1, 4, 4, 9]);
+        write_source("/tmp/test_merge_src_b.parquet", &[2, 4, 7]);
+        write_source("/tmp/test_merge_src_c.parquet", &[3]);
+
+        let output = "/tmp/test_merge_out.parquet";
+        merge_parquet(
+            vec![
+                "/tmp/test_merge_src_a.parquet",
+                "/tmp/test_merge_src_b.parquet",
+                "/tmp/test_merge_src_c.parquet",
+            ],
+            output,
+            smaller
+        ).unwrap();
+
+        let merged = read_ids(output);
+        let mut expected = merged.clone();
+        expected.sort();
+        assert_eq!(merged, expected);
+        assert_eq!(merged, vec![1, 2, 3, 4, 4, 4, 7, 9]);
+    }
+
+    #[test]
+    fn rejects_an_empty_input_list() {
+        let result = merge_parquet(vec![], "/tmp/test_merge_empty.parquet", smaller);
+        assert!(result.is_err());
+    }
+}
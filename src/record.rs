@@ -0,0 +1,64 @@
+use std::sync::Arc;
+use parquet::{
+    record::Row,
+    schema::types::Type
+};
+
+/// Implemented by types that can be appended to a [`crate::rowwritebuffer::RowWriteBuffer`]
+/// without the caller hand-building a `Vec<(String, Field)>` or the matching
+/// `parse_message_type` string.
+///
+/// Do not implement this by hand -- derive it instead:
+///
+/// ```ignore
+/// #[derive(ParquetRecord)]
+/// struct Account {
+///     id: i64,
+///     name: String,
+/// }
+/// ```
+///
+/// `#[derive(ParquetRecord)]` walks the struct fields in declaration order and generates
+/// both `schema()` and `into_row()` from them, so the struct's field order and the Parquet
+/// schema's column order can never drift apart.
+pub trait RecordWriter {
+    /// Builds the `schema::types::Type` message describing this record, in field-declaration order.
+    fn schema() -> Arc<Type>;
+
+    /// Consumes the record and turns it into the `parquet::record::Row` used by
+    /// `RowWriteBuffer::append_row`.
+    fn into_row(self) -> Row;
+}
+
+#[cfg(test)]
+mod tests {
+    use parquet::record::RowAccessor;
+    use parquet_exp_derive::ParquetRecord;
+    use super::RecordWriter;
+
+    #[derive(ParquetRecord)]
+    struct Account {
+        id: i64,
+        name: String,
+        balance: f64,
+        active: bool,
+    }
+
+    #[test]
+    fn schema_and_row_follow_field_declaration_order() {
+        let schema = Account::schema();
+        let field_names: Vec<&str> = schema
+            .get_fields()
+            .iter()
+            .map(|field| field.name())
+            .collect();
+        assert_eq!(field_names, vec!["id", "name", "balance", "active"]);
+
+        let account = Account { id: 7, name: "aafqlr".to_owned(), balance: 12.5, active: true };
+        let row = account.into_row();
+        assert_eq!(row.get_long(0).unwrap(), 7);
+        assert_eq!(row.get_string(1).unwrap(), "aafqlr");
+        assert_eq!(row.get_double(2).unwrap(), 12.5);
+        assert!(row.get_bool(3).unwrap());
+    }
+}
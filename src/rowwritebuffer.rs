@@ -11,6 +11,8 @@ use parquet::{
     schema::types::Type
 };
 use crate::rowwriter;
+use crate::record::RecordWriter;
+use crate::s3_writer::{S3Config, S3Writer};
 
 pub struct RowWriteBuffer {
     max_row_group: usize,
@@ -21,16 +23,26 @@ pub struct RowWriteBuffer {
 
 
 impl RowWriteBuffer {
-    
-    pub fn new(path: &str, schema: Arc<Type>, group_size: usize) -> Result<RowWriteBuffer> {
+
+    /// `s3_config` is only consulted when `path` starts with `s3:`; pass `None` to fall
+    /// back to the usual `AWS_*` environment variables for credentials/region.
+    ///
+    /// `bloom_filter_columns` names the columns (and their target false-positive rate)
+    /// that should get a split block Bloom filter written alongside their min/max
+    /// statistics -- see `rowwriter::RowWriter::from_writer` for how it's sized.
+    pub fn new(path: &str, schema: Arc<Type>, group_size: usize, s3_config: Option<S3Config>, bloom_filter_columns: Vec<(String, f64)>) -> Result<RowWriteBuffer> {
         let (write_sink, rec_buffer) = mpsc::sync_channel(2);
 
         let schema_clone = schema.clone();
         let path_clone = path.to_owned();
 
         let writer_handle = thread::spawn(move || {
-            let writer = create_writer(&path_clone);
-            match rowwriter::RowWriter::channel_writer(rec_buffer, writer, schema_clone) {
+            let writer = create_writer(&path_clone, s3_config.as_ref());
+            let bloom_filter_columns: Vec<(&str, f64)> = bloom_filter_columns
+                .iter()
+                .map(|(name, fpp)| (name.as_str(), *fpp))
+                .collect();
+            match rowwriter::RowWriter::channel_writer(rec_buffer, writer, schema_clone, &bloom_filter_columns) {
                 Ok(()) => println!("File {path_clone:?} written"),
                 Err(err) => println!("Writing file failed with errors {:?}", err)
             }
@@ -42,7 +54,7 @@ impl RowWriteBuffer {
             write_sink,
             writer_handle
         };
-    
+
         Ok(row_writer)
     }
 
@@ -68,6 +80,14 @@ impl RowWriteBuffer {
         }
     }
 
+    /// Appends a value that derives `#[derive(ParquetRecord)]` (and therefore implements
+    /// `RecordWriter`), converting it into a `Row` without the caller ever touching
+    /// `Field`s or `create_row` directly. Build the buffer's schema from `T::schema()`
+    /// so the struct's field order and the Parquet column order stay in lock-step.
+    pub fn append_record<T: RecordWriter>(&mut self, record: T) {
+        self.append_row(record.into_row());
+    }
+
     // pub fn write_duration(&self) -> Duration {
     //     self.duration.clone()
     // }
@@ -75,7 +95,7 @@ impl RowWriteBuffer {
     // Close does consume the writer. 
     // Possibly does this work well when combined with a drop trait?
     pub fn close(mut self)  {
-        if self.buffer.len() > 0 {
+        if !self.buffer.is_empty() {
             if let Err(err) = self.flush() {
                 panic!("auto-Flush on close failed with {err}");
             }
@@ -102,9 +122,9 @@ impl RowWriteBuffer {
 
 
 /// Create a writer based on a string that implements the std::io::Write interface.
-/// If string is prefixed by 'mem:' this will be an in memory buffer, if is is prefixed by 's3:' it will be a s3-object. Otherswise it will be a path on the local file system. 
-fn create_writer(path: &str) -> Box<dyn Write> {
-    let writer: Box<dyn Write> = match path.split(':').next().unwrap() {
+/// If string is prefixed by 'mem:' this will be an in memory buffer, if is is prefixed by 's3:' it will be a s3-object. Otherswise it will be a path on the local file system.
+fn create_writer(path: &str, s3_config: Option<&S3Config>) -> Box<dyn Write + Send> {
+    let writer: Box<dyn Write + Send> = match path.split(':').next().unwrap() {
         prefix if prefix.len() == path.len() => {
                 let file = fs::OpenOptions::new()
 //                    .read(true)
@@ -116,21 +136,32 @@ fn create_writer(path: &str) -> Box<dyn Write> {
                 Box::new(BufWriter::new(file))
             },
         "mem" => Box::new(Vec::new()),
-//        "s3" => println!("{s}: S3"),
+        "s3" => Box::new(S3Writer::new(path, s3_config)),
         prefix => panic!("Unknown prefix '{prefix}' on file {path}")
     };
     writer
 }
 
 
-/// Creates a frow from a series of tuples. This function is based on parquet::record::api::make_row, which is a private function.
-/// A transmute is used to be able to create the rows here. This is a safe step as both parquet::record::Row and RowImitation have the same 
-/// definition, both are compiled with the same compiler, and a struct with only 1 field allows for only a single logical layout.
+/// Builds a `Row` from its field list. `parquet::record::api::make_row`, which does
+/// exactly this, is private to the `parquet` crate, and `Row` exposes no public
+/// constructor that takes arbitrary fields -- there is no safe way to build one outside
+/// `parquet` itself, so this still goes through a same-layout `RowImitation` and a
+/// `transmute`. It is `pub` (and hidden from the docs) because the
+/// `#[derive(ParquetRecord)]`-generated `into_row` (see `crate::record::RecordWriter`)
+/// calls it from whichever crate the struct is defined in; apart from that, only this
+/// crate's own tests call it, and user code never touches a raw `Vec<(String, Field)>`
+/// or this function directly. The const assertion below turns a future `Row` size
+/// change in `parquet` into a compile error here instead of silent UB.
+#[doc(hidden)]
 pub fn create_row(fields: Vec<(String, Field)>) -> Row {
-    
+    #[allow(dead_code)]
     pub struct RowImitation {
         fields: Vec<(String, Field)>,
     }
+
+    const _: () = assert!(mem::size_of::<RowImitation>() == mem::size_of::<Row>());
+
     let row_contents = RowImitation { fields };
     unsafe {mem::transmute(row_contents)}
 }
@@ -140,26 +171,15 @@ pub mod tests {
 
     use std::{
         fs::File,
-        path::Path,
         sync::Arc};
     use parquet::{
-        basic::Compression,
-        data_type::{Int32Type, Int64Type, ByteArrayType, ByteArray},
-        file::{
-            properties::WriterProperties,
-            writer::{
-                SerializedFileWriter,
-                SerializedRowGroupWriter},
-            reader::{
+        file::reader::{
                 SerializedFileReader,
-                FileReader}
-        },
+                FileReader},
         record::{Row, RowAccessor, Field},
-        schema::{parser::parse_message_type,
-            types::Type}
+        schema::parser::parse_message_type
     };
     use crate::rowwritebuffer;
-    use crate::rowiterext;
 
 
     // this is not the right test as I switch to example code
@@ -184,7 +204,7 @@ pub mod tests {
         let schema = Arc::new(parse_message_type(MESSAGE_TYPE).unwrap());
 
 
-        let mut row_writer = rowwritebuffer::RowWriteBuffer::new(path, schema, 10_000).unwrap();
+        let mut row_writer = rowwritebuffer::RowWriteBuffer::new(path, schema, 10_000, None, Vec::new()).unwrap();
 
         for row in input_rows.into_iter() {
             row_writer.append_row(row);
@@ -194,7 +214,8 @@ pub mod tests {
         row_writer.close();
 
         println!("Now open the file {path} and read it again");
-        let result = rowiterext::read_parquet_rowiter(path, Some(10), MESSAGE_TYPE);
+        let reader = SerializedFileReader::new(File::open(path).unwrap()).unwrap();
+        let result: Vec<Row> = reader.get_row_iter(None).unwrap().map(|row| row.unwrap()).collect();
 
         println!("Result of read: {}", result[0]);
         let output_tuples: Vec<(i64, String)> = result
@@ -0,0 +1,146 @@
+use std::io::{self, Write};
+use object_store::aws::AmazonS3Builder;
+use object_store::path::Path as ObjectPath;
+use object_store::WriteMultipart;
+use object_store::ObjectStore;
+use tokio::runtime::{Builder, Runtime};
+
+/// Explicit S3 credentials/region/endpoint for callers who don't want to rely on the
+/// usual `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY` / `AWS_REGION` environment
+/// variables. Any field left as `None` falls back to the environment.
+#[derive(Clone, Default)]
+pub struct S3Config {
+    pub region: Option<String>,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+    pub endpoint: Option<String>,
+}
+
+/// Splits an `s3://bucket/key` path into its bucket and key parts.
+pub(crate) fn parse_s3_path(path: &str) -> (String, String) {
+    let rest = path
+        .strip_prefix("s3://")
+        .unwrap_or_else(|| panic!("Malformed s3 path '{path}', expected 's3://bucket/key'"));
+    let (bucket, key) = rest
+        .split_once('/')
+        .unwrap_or_else(|| panic!("Malformed s3 path '{path}', missing key after bucket"));
+    (bucket.to_owned(), key.to_owned())
+}
+
+/// A `std::io::Write` sink that multipart-uploads everything written to it to an S3
+/// object. Runs its own single-threaded Tokio runtime so it can be driven from the
+/// plain OS thread `RowWriteBuffer::new` spawns for the writer, without requiring an
+/// ambient async runtime there.
+pub struct S3Writer {
+    rt: Runtime,
+    upload: Option<WriteMultipart>,
+}
+
+impl S3Writer {
+    pub fn new(path: &str, config: Option<&S3Config>) -> Self {
+        let (bucket, key) = parse_s3_path(path);
+
+        let mut builder = AmazonS3Builder::from_env().with_bucket_name(&bucket);
+        if let Some(config) = config {
+            if let Some(region) = &config.region {
+                builder = builder.with_region(region);
+            }
+            if let Some(access_key_id) = &config.access_key_id {
+                builder = builder.with_access_key_id(access_key_id);
+            }
+            if let Some(secret_access_key) = &config.secret_access_key {
+                builder = builder.with_secret_access_key(secret_access_key);
+            }
+            if let Some(endpoint) = &config.endpoint {
+                builder = builder.with_endpoint(endpoint);
+            }
+        }
+        let store = builder.build().expect("failed to build S3 client");
+
+        let rt = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to start S3 writer runtime");
+        let object_path = ObjectPath::from(key);
+        let upload = rt.block_on(async {
+            let multipart = store
+                .put_multipart(&object_path)
+                .await
+                .expect("failed to start multipart upload");
+            WriteMultipart::new(multipart)
+        });
+
+        S3Writer { rt, upload: Some(upload) }
+    }
+
+    /// Finalizes the multipart upload. For a bare `S3Writer` this is the path to use:
+    /// it surfaces upload errors through a `Result` instead of the `eprintln!` the
+    /// `Drop` impl below is limited to. `RowWriteBuffer`'s writer thread never gets the
+    /// chance to call this explicitly -- the `Box<dyn Write>` it builds its `RowWriter`
+    /// from is moved into the thread closure and dropped there once writing finishes,
+    /// so for that path `Drop` below is the actual finalization mechanism, not a
+    /// fallback.
+    pub fn close(mut self) -> io::Result<()> {
+        if let Some(upload) = self.upload.take() {
+            self.rt
+                .block_on(upload.finish())
+                .map(|_| ())
+                .map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for S3Writer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let upload = self.upload.as_mut().expect("write after close");
+        // `WriteMultipart::write` schedules the part upload via `tokio::spawn`, which
+        // panics ("no reactor running") without an entered runtime context -- `write`
+        // is called from the plain OS thread `RowWriteBuffer::new` spawns, with no
+        // ambient runtime, so it must be entered explicitly here (every other call
+        // into `object_store`/tokio in this file goes through `rt.block_on` instead,
+        // which isn't an option for a synchronous, non-async `Write::write`).
+        let _guard = self.rt.enter();
+        upload.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for S3Writer {
+    fn drop(&mut self) {
+        if let Some(upload) = self.upload.take() {
+            if let Err(err) = self.rt.block_on(upload.finish()) {
+                eprintln!("S3Writer dropped without close(): multipart upload failed: {err}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_s3_path;
+
+    #[test]
+    fn splits_bucket_and_key() {
+        assert_eq!(
+            parse_s3_path("s3://my-bucket/some/nested/key.parquet"),
+            ("my-bucket".to_owned(), "some/nested/key.parquet".to_owned())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 's3://bucket/key'")]
+    fn rejects_missing_scheme() {
+        parse_s3_path("my-bucket/key.parquet");
+    }
+
+    #[test]
+    #[should_panic(expected = "missing key after bucket")]
+    fn rejects_missing_key() {
+        parse_s3_path("s3://my-bucket");
+    }
+}
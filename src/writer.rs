@@ -3,11 +3,11 @@ use std::{
     io::Write,
     path::Path,
     slice::Iter,
-    sync::Arc
+    sync::{Arc, mpsc::Receiver}
 };
 use parquet::{
     basic::{Compression, ConvertedType, Type as PhysicalType},
-    data_type::{Int32Type, Int64Type, ByteArrayType, ByteArray},
+    data_type::{AsBytes, Int32Type, Int64Type, FloatType, DoubleType, BoolType, ByteArrayType, ByteArray},
     errors::Result,
     file::{
         properties::WriterProperties,
@@ -16,9 +16,8 @@ use parquet::{
     record::{Row,
         RowAccessor
     },
-    schema::types::Type
+    schema::types::{ColumnPath, Type}
 };
-use super::ttypes;
 
 
 
@@ -31,30 +30,100 @@ pub struct RowWriter<W: Write>{
 }
 
 
-impl<W: Write> RowWriter::<W> {
-    pub fn new(path: &Path, schema: Arc<Type>, num_recs: u64, group_size: usize) -> Result<RowWriter<fs::File>> {
-        let props = Arc::new(WriterProperties::builder()
-            .set_compression(Compression::SNAPPY)
-            .build());
-        let file = fs::File::create(&path).unwrap();
+impl RowWriter<fs::File> {
+    pub fn new(path: &Path, schema: Arc<Type>, _num_recs: u64, group_size: usize, bloom_filter_columns: &[(&str, f64)]) -> Result<RowWriter<fs::File>> {
+        let file = fs::File::create(path).unwrap();
+        RowWriter::<fs::File>::from_writer(file, schema, group_size, bloom_filter_columns)
+    }
+}
+
+impl<W: Write + Send> RowWriter::<W> {
+    /// Same as `new`, but writes through an arbitrary `W: Write` instead of always
+    /// opening a `fs::File`. Lets callers (e.g. `AsyncRowWriteBuffer`) encode a row
+    /// group into an in-memory buffer rather than a file on disk.
+    ///
+    /// `bloom_filter_columns` names the columns that should get a split block Bloom
+    /// filter (SBBF) at the given target false-positive rate, to speed up equality
+    /// lookups on those columns. The actual accumulation of
+    /// inserted values into the filter's blocks, and writing the filter bytes plus its
+    /// offset/length into the column chunk metadata, is handled by `parquet`'s column
+    /// writer as rows are written -- it sizes `num_blocks` from `fpp` and `ndv` with the
+    /// same formula used for the SBBF itself (`-8 * ndv / ln(1 - fpp^(1/8))`, rounded up
+    /// to a power of two). We don't track distinct values per column, so `ndv` is set to
+    /// `group_size`, the row group's row count -- an upper bound on distinct values that
+    /// keeps the filter sized for the data actually being written instead of whatever
+    /// default `ndv` the `parquet` crate otherwise falls back to.
+    pub fn from_writer(writer: W, schema: Arc<Type>, group_size: usize, bloom_filter_columns: &[(&str, f64)]) -> Result<RowWriter<W>> {
+        let mut props_builder = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY);
+
+        for (column, fpp) in bloom_filter_columns {
+            let column_path = ColumnPath::from(*column);
+            props_builder = props_builder
+                .set_column_bloom_filter_enabled(column_path.clone(), true)
+                .set_column_bloom_filter_fpp(column_path.clone(), *fpp)
+                .set_column_bloom_filter_ndv(column_path, group_size as u64);
+        }
+
+        let props = Arc::new(props_builder.build());
         let schema_clone = schema.clone();
 
-        let row_writer = RowWriter::<fs::File> {
-            row_writer: SerializedFileWriter::<_>::new(file, schema, props).unwrap(),
+        let row_writer = RowWriter::<W> {
+            row_writer: SerializedFileWriter::<_>::new(writer, schema, props).unwrap(),
             max_row_group: group_size,
             buffer: Vec::with_capacity(group_size),
             schema: schema_clone
         };
-    
+
         Ok(row_writer)
     }
 
+    /// Drains `rec_buffer` until the sending half closes (`RowWriteBuffer::close` drops
+    /// its `write_sink` once its own buffer is flushed), writing each received batch as
+    /// its own row group, then finalizes the file. This is the body of the background
+    /// thread `RowWriteBuffer::new` spawns, so the `SerializedFileWriter` lives and dies
+    /// entirely on that thread and never has to cross back over the channel.
+    ///
+    /// Batches already arrive pre-sized to `RowWriteBuffer`'s own `max_row_group`, so
+    /// each one is written as a row group as-is rather than re-buffered against a second
+    /// `group_size` here; the first non-empty batch's length seeds `from_writer`'s bloom
+    /// filter `ndv` sizing.
+    pub fn channel_writer(rec_buffer: Receiver<Vec<Row>>, writer: W, schema: Arc<Type>, bloom_filter_columns: &[(&str, f64)]) -> Result<()> {
+        let mut writer = Some(writer);
+        let mut row_writer: Option<RowWriter<W>> = None;
+
+        for batch in rec_buffer {
+            if batch.is_empty() {
+                continue;
+            }
+
+            if row_writer.is_none() {
+                let writer = writer.take().expect("channel_writer: writer already handed to a RowWriter");
+                row_writer = Some(RowWriter::from_writer(writer, schema.clone(), batch.len(), bloom_filter_columns)?);
+            }
+
+            let row_writer = row_writer.as_mut().expect("just initialized above");
+            row_writer.buffer.extend(batch);
+            row_writer.flush()?;
+            row_writer.buffer.clear();
+        }
+
+        if let Some(row_writer) = row_writer {
+            row_writer.close()?;
+        }
+        Ok(())
+    }
+
     pub fn remaining_space(&self) -> usize {
         self.max_row_group - self.buffer.len()
     }
 
 
     pub fn flush(&mut self) -> Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
         let mut row_group_writer = self.row_writer.next_row_group().unwrap();
 
         for (idx, field) in self.schema.get_fields().iter().enumerate() {
@@ -63,27 +132,35 @@ impl<W: Write> RowWriter::<W> {
                 match field.get_basic_info().converted_type() {
                     ConvertedType::INT_64 => write_i64_column(self.buffer.iter(), idx, &mut col_writer)?,
                     ConvertedType::UTF8 => write_utf8_column(self.buffer.iter(), idx, &mut col_writer)?,
-                    ConvertedType::TIMESTAMP_MILLIS => {
-    
-                    },
-                    // some more types need to be implemented
+                    ConvertedType::TIMESTAMP_MILLIS | ConvertedType::TIMESTAMP_MICROS =>
+                        write_i64_column(self.buffer.iter(), idx, &mut col_writer)?,
+                    ConvertedType::DATE => write_i32_column(self.buffer.iter(), idx, &mut col_writer)?,
                     ConvertedType::NONE => {
                         match field.get_physical_type() {
+                            PhysicalType::INT32 => write_i32_column(self.buffer.iter(), idx, &mut col_writer)?,
                             PhysicalType::INT64 => write_i64_column(self.buffer.iter(), idx, &mut col_writer)?,
+                            PhysicalType::BOOLEAN => write_bool_column(self.buffer.iter(), idx, &mut col_writer)?,
+                            PhysicalType::FLOAT => write_float_column(self.buffer.iter(), idx, &mut col_writer)?,
+                            PhysicalType::DOUBLE => write_double_column(self.buffer.iter(), idx, &mut col_writer)?,
+                            PhysicalType::BYTE_ARRAY => write_byte_array_column(self.buffer.iter(), idx, &mut col_writer)?,
                             _ => {
                                 panic!("Column {idx}: Unknown Pysical-type {:?}", field.get_physical_type());
                             }
                         }
                     },
-                    // some more types need to be implemented
                     _ => panic!("Column {idx}: Unknown Converted-type {:?}", field.get_basic_info().converted_type())
-                }    
+                }
+                // `next_column`'s `Drop` impl does not close the column writer on its
+                // own -- `SerializedRowGroupWriter::close` asserts the previous column
+                // was closed and errors out otherwise, so each column chunk must be
+                // finalized explicitly before moving on to the next one.
+                col_writer.close()?;
             } else {
                 panic!("Could not find a column-writer for column {idx} containing {:#?}", field)
             }
-            
- 
         }
+
+        row_group_writer.close()?;
         Ok(())
     }
 
@@ -98,8 +175,11 @@ impl<W: Write> RowWriter::<W> {
     }
 
 
-    pub fn close() {
-
+    /// Finalizes the underlying `SerializedFileWriter`, writing the footer, and hands
+    /// back the inner `W` so callers that wrote into an in-memory buffer can get at
+    /// the encoded bytes.
+    pub fn close(self) -> Result<W> {
+        self.row_writer.into_inner()
     }
 
 }
@@ -115,7 +195,7 @@ fn write_i64_column(rows: Iter<Row>,  idx: usize, col_writer: &mut SerializedCol
 
     col_writer
         .typed::<Int64Type>()
-        .write_batch_with_statistics(&column, None, None, Some(&the_min), Some(&the_max), None)?;
+        .write_batch_with_statistics(&column, None, None, Some(the_min), Some(the_max), None)?;
     Ok(())
 }
 
@@ -125,12 +205,176 @@ fn write_utf8_column(rows: Iter<Row>, idx: usize, col_writer: &mut SerializedCol
     let column: Vec<ByteArray> = rows
         .map(|row| row.get_string(idx).unwrap().as_str().into())
         .collect();
-//        let the_min = column.iter().min().unwrap();
-//        let the_max = column.iter().max().unwrap();
+    let the_min = column.iter().min_by_key(|value| value.as_bytes()).unwrap();
+    let the_max = column.iter().max_by_key(|value| value.as_bytes()).unwrap();
+
+    col_writer
+        .typed::<ByteArrayType>()
+        .write_batch_with_statistics(&column, None, None, Some(the_min), Some(the_max), None)?;
+    Ok(())
+}
+
+
+fn write_byte_array_column(rows: Iter<Row>, idx: usize, col_writer: &mut SerializedColumnWriter) -> Result<()> {
+    let column: Vec<ByteArray> = rows
+        .map(|row| row.get_bytes(idx).unwrap().clone())
+        .collect();
+    let the_min = column.iter().min_by_key(|value| value.as_bytes()).unwrap();
+    let the_max = column.iter().max_by_key(|value| value.as_bytes()).unwrap();
 
     col_writer
         .typed::<ByteArrayType>()
-        .write_batch_with_statistics(&column, None, None, Some(&(column[0])), column.last(), None)?;
+        .write_batch_with_statistics(&column, None, None, Some(the_min), Some(the_max), None)?;
+    Ok(())
+}
+
+
+fn write_i32_column(rows: Iter<Row>, idx: usize, col_writer: &mut SerializedColumnWriter) -> Result<()> {
+    let column: Vec<i32> = rows
+        .map(|row| row.get_int(idx).unwrap())
+        .collect();
+    let the_min = column.iter().min().unwrap();
+    let the_max = column.iter().max().unwrap();
+
+    col_writer
+        .typed::<Int32Type>()
+        .write_batch_with_statistics(&column, None, None, Some(the_min), Some(the_max), None)?;
+    Ok(())
+}
+
+
+fn write_bool_column(rows: Iter<Row>, idx: usize, col_writer: &mut SerializedColumnWriter) -> Result<()> {
+    let column: Vec<bool> = rows
+        .map(|row| row.get_bool(idx).unwrap())
+        .collect();
+    let the_min = column.iter().min().unwrap();
+    let the_max = column.iter().max().unwrap();
+
+    col_writer
+        .typed::<BoolType>()
+        .write_batch_with_statistics(&column, None, None, Some(the_min), Some(the_max), None)?;
+    Ok(())
+}
+
+
+fn write_float_column(rows: Iter<Row>, idx: usize, col_writer: &mut SerializedColumnWriter) -> Result<()> {
+    let column: Vec<f32> = rows
+        .map(|row| row.get_float(idx).unwrap())
+        .collect();
+    let the_min = column.iter().cloned().reduce(f32::min).unwrap();
+    let the_max = column.iter().cloned().reduce(f32::max).unwrap();
+
+    col_writer
+        .typed::<FloatType>()
+        .write_batch_with_statistics(&column, None, None, Some(&the_min), Some(&the_max), None)?;
     Ok(())
 }
 
+
+fn write_double_column(rows: Iter<Row>, idx: usize, col_writer: &mut SerializedColumnWriter) -> Result<()> {
+    let column: Vec<f64> = rows
+        .map(|row| row.get_double(idx).unwrap())
+        .collect();
+    let the_min = column.iter().cloned().reduce(f64::min).unwrap();
+    let the_max = column.iter().cloned().reduce(f64::max).unwrap();
+
+    col_writer
+        .typed::<DoubleType>()
+        .write_batch_with_statistics(&column, None, None, Some(&the_min), Some(&the_max), None)?;
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path, sync::Arc};
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::{Field, RowAccessor},
+        schema::parser::parse_message_type
+    };
+    use crate::rowwritebuffer::create_row;
+    use super::RowWriter;
+
+    const MESSAGE_TYPE: &str = "
+        message schema {
+            REQUIRED INT64 id;
+            REQUIRED BINARY account (UTF8);
+        }
+    ";
+
+    #[test]
+    fn flush_writes_a_bloom_filter_for_the_requested_column() {
+        let schema = Arc::new(parse_message_type(MESSAGE_TYPE).unwrap());
+        let path = Path::new("/tmp/test_bloom_filter_column.parquet");
+
+        let mut writer = RowWriter::new(path, schema, 3, 3, &[("id", 0.01)]).unwrap();
+        for (id, account) in [(1_i64, "Hello"), (2, "World"), (3, "!")] {
+            writer.append_row(create_row(vec![
+                ("id".to_owned(), Field::Long(id)),
+                ("account".to_owned(), Field::Str(account.to_owned())),
+            ]));
+        }
+        writer.close().unwrap();
+
+        let reader = SerializedFileReader::new(fs::File::open(path).unwrap()).unwrap();
+        let row_group = reader.metadata().row_group(0);
+        let id_column = row_group.column(0);
+        let account_column = row_group.column(1);
+
+        assert!(id_column.bloom_filter_offset().is_some(), "id column should have a bloom filter");
+        assert!(account_column.bloom_filter_offset().is_none(), "account column was not requested to have a bloom filter");
+    }
+
+    #[test]
+    fn flush_round_trips_every_physical_column_type() {
+        const TYPES: &str = "
+            message schema {
+                REQUIRED INT32 age;
+                REQUIRED BOOLEAN active;
+                REQUIRED FLOAT score;
+                REQUIRED DOUBLE balance;
+                REQUIRED BINARY payload;
+            }
+        ";
+        let schema = Arc::new(parse_message_type(TYPES).unwrap());
+        let path = Path::new("/tmp/test_all_physical_types.parquet");
+
+        let rows = [
+            (21_i32, true, 1.5_f32, 10.5_f64, b"aa".to_vec()),
+            (34, false, -2.5, -20.25, b"zz".to_vec()),
+            (5, true, 0.0, 0.0, b"mm".to_vec()),
+        ];
+
+        let mut writer = RowWriter::new(path, schema, rows.len() as u64, rows.len(), &[]).unwrap();
+        for (age, active, score, balance, ref payload) in rows.clone() {
+            writer.append_row(create_row(vec![
+                ("age".to_owned(), Field::Int(age)),
+                ("active".to_owned(), Field::Bool(active)),
+                ("score".to_owned(), Field::Float(score)),
+                ("balance".to_owned(), Field::Double(balance)),
+                ("payload".to_owned(), Field::Bytes(payload.clone().into())),
+            ]));
+        }
+        writer.close().unwrap();
+
+        let reader = SerializedFileReader::new(fs::File::open(path).unwrap()).unwrap();
+        let read_back: Vec<(i32, bool, f32, f64, Vec<u8>)> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| {
+                let row = row.unwrap();
+                (
+                    row.get_int(0).unwrap(),
+                    row.get_bool(1).unwrap(),
+                    row.get_float(2).unwrap(),
+                    row.get_double(3).unwrap(),
+                    row.get_bytes(4).unwrap().data().to_vec(),
+                )
+            })
+            .collect();
+
+        assert_eq!(read_back, rows.to_vec());
+    }
+}
+